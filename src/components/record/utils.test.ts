@@ -43,6 +43,20 @@ function createMockDriver(values: Values[]): Driver {
   };
 }
 
+// Push a 1 Hz value into buf, skipping the samples strictly inside each gap.
+function fillEverySecond(
+  buf: CircularBuffer<Value>,
+  start: number,
+  fromMs: number,
+  toMs: number,
+  gaps: [number, number][],
+) {
+  for (let t = fromMs; t <= toMs; t += 1000) {
+    if (gaps.some(([from, to]) => t > from && t < to)) continue;
+    buf.push({ timestamp: new Date(start + t), value: 60 });
+  }
+}
+
 describe("startStreaming", () => {
   let valuesRef: React.RefObject<CircularBuffer<Value>[]>;
   let mockDriver: Driver;
@@ -183,8 +197,8 @@ describe("startEDFWriterLoop", () => {
     writeRecord = vi.fn().mockResolvedValue(undefined);
     edfWriter = { writeRecord };
     signals = [
-      { samplesPerRecord: 2 } as EDFSignal,
-      { samplesPerRecord: 2 } as EDFSignal,
+      { label: "Signal1", samplesPerRecord: 2 } as EDFSignal,
+      { label: "Signal2", samplesPerRecord: 2 } as EDFSignal,
     ];
 
     onError = vi.fn();
@@ -263,4 +277,144 @@ describe("startEDFWriterLoop", () => {
       [0, 0],
     ]);
   });
+
+  it("annotates signals with no values in the epoch", async () => {
+    const buf0 = new CircularBuffer<Value>(4);
+    valuesRef = { current: [buf0, new CircularBuffer<Value>(4)] };
+    signals = [
+      { label: "Heart Rate", samplesPerRecord: 2 } as EDFSignal,
+      { label: "Temperature", samplesPerRecord: 2 } as EDFSignal,
+    ];
+
+    stop = startEDFWriterLoop({
+      edfWriter,
+      signals,
+      valuesRef,
+      onError,
+    });
+
+    // Only the first signal receives a value during the epoch
+    vi.advanceTimersByTime(EPOCH_DURATION_MS - 100);
+    buf0.push({ timestamp: new Date(), value: 60 });
+    vi.advanceTimersByTime(105);
+    await Promise.resolve();
+
+    expect(writeRecord).toHaveBeenCalledTimes(1);
+    expect(writeRecord.mock.calls[0][1]).toEqual([
+      {
+        onset: 0,
+        duration: EPOCH_DURATION_MS / 1000,
+        annotation: "Signal gap: Temperature",
+      },
+    ]);
+  });
+
+  it("annotates the real onset of a gap within a later epoch", async () => {
+    const start = Date.now();
+    const buf = new CircularBuffer<Value>(128);
+    valuesRef = { current: [buf] };
+    signals = [{ label: "Heart Rate", samplesPerRecord: 30 } as EDFSignal];
+
+    // Samples missing between 40 s and 50 s
+    const fill = (fromMs: number, toMs: number) =>
+      fillEverySecond(buf, start, fromMs, toMs, [[40000, 50000]]);
+
+    stop = startEDFWriterLoop({
+      edfWriter,
+      signals,
+      valuesRef,
+      onError,
+    });
+
+    fill(0, EPOCH_DURATION_MS);
+    vi.advanceTimersByTime(EPOCH_DURATION_MS);
+    await Promise.resolve();
+    fill(EPOCH_DURATION_MS + 1000, 2 * EPOCH_DURATION_MS);
+    vi.advanceTimersByTime(EPOCH_DURATION_MS);
+    await Promise.resolve();
+
+    expect(writeRecord).toHaveBeenCalledTimes(2);
+    expect(writeRecord.mock.calls[0][1]).toEqual([]);
+    expect(writeRecord.mock.calls[1][1]).toEqual([
+      { onset: 41, duration: 9, annotation: "Signal gap: Heart Rate" },
+    ]);
+
+    // The missing samples are zero-filled, the rest are kept.
+    const record = writeRecord.mock.calls[1][0][0];
+    expect(record.slice(10, 21)).toEqual([60, ...Array(9).fill(0), 60]);
+  });
+
+  it("annotates each dropout in an epoch separately", async () => {
+    const start = Date.now();
+    const buf = new CircularBuffer<Value>(64);
+    valuesRef = { current: [buf] };
+    signals = [
+      { label: "ChestWitmotionAccX", samplesPerRecord: 30 } as EDFSignal,
+    ];
+
+    stop = startEDFWriterLoop({
+      edfWriter,
+      signals,
+      valuesRef,
+      onError,
+    });
+
+    fillEverySecond(buf, start, 0, EPOCH_DURATION_MS, [
+      [2000, 7000],
+      [20000, 25000],
+    ]);
+    vi.advanceTimersByTime(EPOCH_DURATION_MS);
+    await Promise.resolve();
+
+    // Labels are capped at the 16 characters EDF allows
+    expect(writeRecord.mock.calls[0][1]).toEqual([
+      { onset: 3, duration: 4, annotation: "Signal gap: ChestWitmotionAc" },
+      { onset: 21, duration: 4, annotation: "Signal gap: ChestWitmotionAc" },
+    ]);
+  });
+
+  it("merges the closest dropouts beyond the annotation budget", async () => {
+    const start = Date.now();
+    const buf = new CircularBuffer<Value>(64);
+    valuesRef = { current: [buf] };
+    signals = [{ label: "Heart Rate", samplesPerRecord: 30 } as EDFSignal];
+
+    stop = startEDFWriterLoop({
+      edfWriter,
+      signals,
+      valuesRef,
+      onError,
+    });
+
+    fillEverySecond(buf, start, 0, EPOCH_DURATION_MS, [
+      [2000, 7000],
+      [10000, 14000],
+      [20000, 25000],
+    ]);
+    vi.advanceTimersByTime(EPOCH_DURATION_MS);
+    await Promise.resolve();
+
+    expect(writeRecord.mock.calls[0][1]).toEqual([
+      { onset: 3, duration: 11, annotation: "Signal gap: Heart Rate" },
+      { onset: 21, duration: 4, annotation: "Signal gap: Heart Rate" },
+    ]);
+  });
+
+  it("omits annotations when every signal has values", async () => {
+    stop = startEDFWriterLoop({
+      edfWriter,
+      signals,
+      valuesRef,
+      onError,
+    });
+
+    vi.advanceTimersByTime(EPOCH_DURATION_MS - 100);
+    valuesRef.current.forEach((buf) =>
+      buf.push({ timestamp: new Date(), value: 1 }),
+    );
+    vi.advanceTimersByTime(105);
+    await Promise.resolve();
+
+    expect(writeRecord.mock.calls[0][1]).toEqual([]);
+  });
 });
@@ -14,10 +14,10 @@
  */
 
 import type { Driver } from "@/lib/drivers/driver";
-import type { EDFSignal } from "@/lib/edf/edftypes";
+import type { EDFAnnotation, EDFSignal } from "@/lib/edf/edftypes";
 import { EDFWriter } from "@/lib/edf/edfwriter";
 import type { Values, Value } from "@/lib/types";
-import { resample } from "@/lib/resampling/linear";
+import { resampleToGrid } from "@/lib/resampling/grid";
 import { EPOCH_DURATION_MS } from "@/lib/constants";
 import { CircularBuffer } from "@/lib/containers/circular-buffer";
 
@@ -48,6 +48,93 @@ export const startStreaming = (
   })();
 };
 
+// Gaps longer than this many sample periods, or MIN_GAP_MS, are annotated.
+const GAP_PERIODS = 3;
+const MIN_GAP_MS = 2000;
+
+// EDFWriter reserves 128 bytes of annotations per signal, which holds two gap
+// TALs as long as labels are capped at the 16 characters EDF allows.
+const MAX_GAPS_PER_RECORD = 2;
+const EDF_LABEL_LENGTH = 16;
+
+// Collect the values since fromTime, plus the last one before it so the start
+// of the epoch can be interpolated.
+// Scan buffer newest -> oldest and stop once we cross the boundary.
+const recentValues = (
+  buf: CircularBuffer<Value> | undefined,
+  fromTime: number,
+): Values => {
+  if (!buf || buf.size === 0) return [];
+  const out: Value[] = [];
+  for (let i = buf.size - 1; i >= 0; i--) {
+    const v = buf.at(i);
+    out.push(v);
+    if (v.timestamp.getTime() < fromTime) break;
+  }
+  out.reverse(); // restore chronological order (oldest -> newest)
+  return out;
+};
+
+// Resample values onto the record's sample grid, starting at fromTime.
+// Points that aren't within maxGapMs of real data are NaN.
+const toRecordGrid = (
+  values: Values,
+  fromTime: number,
+  periodMs: number,
+  maxGapMs: number,
+  samples: number,
+): number[] => {
+  if (values.length === 0) return new Array(samples).fill(NaN);
+
+  const grid = resampleToGrid(values, periodMs, maxGapMs, fromTime).map(
+    (v) => v.value,
+  );
+
+  // The grid stops at the last value and is NaN before the first, hold the
+  // edge values as long as they're recent enough.
+  const first = values[0];
+  const last = values[values.length - 1];
+  const out: number[] = [];
+  for (let k = 0; k < samples; k++) {
+    const time = fromTime + k * periodMs;
+    const value = grid[k] ?? NaN;
+    if (!Number.isNaN(value)) {
+      out.push(value);
+    } else if (
+      time < first.timestamp.getTime() &&
+      first.timestamp.getTime() - time <= maxGapMs
+    ) {
+      out.push(first.value);
+    } else if (
+      time > last.timestamp.getTime() &&
+      time - last.timestamp.getTime() <= maxGapMs
+    ) {
+      out.push(last.value);
+    } else {
+      out.push(NaN);
+    }
+  }
+  return out;
+};
+
+// Merge the gaps separated by the least real data until at most max remain.
+// Runs are [first, last] grid indexes, in order.
+const mergeGaps = (
+  runs: [number, number][],
+  max: number,
+): [number, number][] => {
+  while (runs.length > max) {
+    let best = 0;
+    for (let i = 1; i < runs.length - 1; i++) {
+      if (runs[i + 1][0] - runs[i][1] < runs[best + 1][0] - runs[best][1]) {
+        best = i;
+      }
+    }
+    runs.splice(best, 2, [runs[best][0], runs[best + 1][1]]);
+  }
+  return runs;
+};
+
 export const startEDFWriterLoop = ({
   edfWriter,
   signals,
@@ -59,40 +146,58 @@ export const startEDFWriterLoop = ({
   valuesRef: React.RefObject<CircularBuffer<Value>[]>;
   onError: (err: Error) => void;
 }): (() => void) => {
+  let recordNumber = 0;
+
   const interval = setInterval(async () => {
     try {
       const now = Date.now();
       const epochMs = EPOCH_DURATION_MS;
       const fromTime = now - epochMs;
 
-      const samplesPerRecordList = signals.map((s) => s.samplesPerRecord);
-
-      // For each signal, collect samples within the last epoch.
-      // Scan buffer newest -> oldest and stop once we cross the boundary.
-      const recentPerSignal: Values[] = valuesRef.current.map((buf) => {
-        if (!buf || buf.size === 0) return [];
-        const out: Value[] = [];
-        for (let i = buf.size - 1; i >= 0; i--) {
-          const v = buf.at(i);
-          if (v.timestamp.getTime() >= fromTime) {
-            out.push(v);
-          } else {
-            break;
-          }
-        }
-        out.reverse(); // restore chronological order (oldest -> newest)
-        return out;
-      });
+      const recordStart = (recordNumber * epochMs) / 1000;
+      const resampled: number[][] = [];
+      const annotations: EDFAnnotation[] = [];
+
+      signals.forEach((signal, i) => {
+        const samples = signal.samplesPerRecord;
+        const periodMs = epochMs / samples;
+        // Anything much longer than a sample period (or a 1 Hz keep-alive)
+        // means the sensor stopped delivering data.
+        const maxGapMs = Math.max(GAP_PERIODS * periodMs, MIN_GAP_MS);
+
+        const grid = toRecordGrid(
+          recentValues(valuesRef.current[i], fromTime),
+          fromTime,
+          periodMs,
+          maxGapMs,
+          samples,
+        );
 
-      // Resample each signal to the EDF record length, then extract numeric values
-      const resampled: number[][] = recentPerSignal.map((vals, i) => {
-        const samples = samplesPerRecordList[i];
-        if (vals.length === 0) return new Array(samples).fill(0);
-        const r = resample(vals, samples);
-        return r.map((v) => v.value);
+        // Zero-fill gaps, and mark each one so the fill isn't mistaken for
+        // real data.
+        const runs: [number, number][] = [];
+        resampled.push(
+          grid.map((value, k) => {
+            if (!Number.isNaN(value)) return value;
+            const run = runs[runs.length - 1];
+            if (run && run[1] === k - 1) run[1] = k;
+            else runs.push([k, k]);
+            return 0;
+          }),
+        );
+
+        const label = signal.label.slice(0, EDF_LABEL_LENGTH);
+        for (const [first, last] of mergeGaps(runs, MAX_GAPS_PER_RECORD)) {
+          annotations.push({
+            onset: recordStart + (first * periodMs) / 1000,
+            duration: ((last - first + 1) * periodMs) / 1000,
+            annotation: `Signal gap: ${label}`,
+          });
+        }
       });
 
-      await edfWriter.writeRecord(resampled);
+      await edfWriter.writeRecord(resampled, annotations);
+      recordNumber += 1;
     } catch (err) {
       onError(err instanceof Error ? err : new Error(String(err)));
     }
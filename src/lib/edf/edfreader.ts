@@ -44,7 +44,7 @@ export class EDFReader {
     const startTimeStr = headerText.substring(176, 184).trim();
     const headerBytes = parseInt(headerText.substring(184, 192).trim());
     const reserved = headerText.substring(192, 236).trim();
-    const dataRecords = parseInt(headerText.substring(236, 244).trim());
+    let dataRecords = parseInt(headerText.substring(236, 244).trim());
     const recordDuration = parseFloat(headerText.substring(244, 252).trim());
     const signalCount = parseInt(headerText.substring(252, 256).trim());
 
@@ -86,6 +86,19 @@ export class EDFReader {
       signals.push(signal as EDFSignal);
     }
 
+    // Files that were never finalized record -1, infer the count from the
+    // number of complete records present.
+    if (dataRecords === -1) {
+      const recordSize = signals.reduce(
+        (sum, s) => sum + s.samplesPerRecord * 2,
+        0,
+      );
+      dataRecords =
+        recordSize > 0
+          ? Math.floor((this.byteArray.length - headerBytes) / recordSize)
+          : 0;
+    }
+
     this.header = {
       version,
       patientId,
@@ -18,8 +18,9 @@ import path from "path";
 import { EDFWriter } from "./edfwriter";
 import { EDFReader } from "./edfreader";
 import type { EDFHeader, EDFSignal, EDFAnnotation } from "./edftypes";
-import { describe, expect, it } from "vitest";
+import { describe, expect, it, vi } from "vitest";
 import { MemoryWritableStream } from "@/lib/stream";
+import { sine, whiteNoise } from "@/lib/signal-gen";
 
 function createTestHeader(signalCount = 1, records = 1): EDFHeader {
   const signals: EDFSignal[] = Array(signalCount)
@@ -52,6 +53,39 @@ function createTestHeader(signalCount = 1, records = 1): EDFHeader {
   };
 }
 
+// Write one record of a single ±100 µV signal.
+async function writeSingleRecord(values: number[]): Promise<Uint8Array> {
+  const header = createTestHeader(1, 1);
+  header.signals[0].samplesPerRecord = values.length;
+
+  const { writer, getBuffer } = MemoryWritableStream();
+  const edfWriter = new EDFWriter(writer);
+  await edfWriter.writeHeader(header);
+  await edfWriter.writeRecord([values]);
+  await edfWriter.close();
+  return getBuffer();
+}
+
+// Digital codes of the first signal in the first record, read straight from the
+// file. The header is 256 bytes plus 256 per signal, here the test signal and
+// the annotation signal.
+function readFirstSignalCodes(buffer: Uint8Array, samples: number): number[] {
+  const view = new DataView(
+    buffer.buffer,
+    buffer.byteOffset,
+    buffer.byteLength,
+  );
+  const offset = 256 + 256 * 2;
+  return Array.from({ length: samples }, (_, i) =>
+    view.getInt16(offset + i * 2, true),
+  );
+}
+
+// ±100 µV maps onto the full 16-bit range. Rounding -0.5 gives -0, which the
+// file stores as 0.
+const expectedCode = (v: number): number =>
+  Math.round(((v + 100) * 65535) / 200 - 32768) || 0;
+
 function expectToBeImprecise(
   actual: number[],
   expected: number[],
@@ -89,6 +123,55 @@ describe("EDFWriter", () => {
     expectToBeImprecise(readValues, values[0]);
   });
 
+  it("rewrites the record count on close for seekable streams", async () => {
+    // The caller's guess of 5 records is replaced by the real count.
+    const header = createTestHeader(1, 5);
+
+    const { writer, getBuffer } = MemoryWritableStream();
+    const edfWriter = new EDFWriter(writer);
+    await edfWriter.writeHeader(header);
+    await edfWriter.writeRecord([new Array(10).fill(1)]);
+    await edfWriter.writeRecord([new Array(10).fill(2)]);
+    await edfWriter.close();
+
+    const buffer = await getBuffer();
+    const text = new TextDecoder().decode(buffer.subarray(236, 244));
+    expect(text.trim()).toBe("2");
+  });
+
+  it("writes an unknown record count for non-seekable streams", async () => {
+    const chunks: Uint8Array[] = [];
+    const stream = new WritableStream<Uint8Array>({
+      write(chunk) {
+        chunks.push(chunk);
+      },
+    });
+
+    const edfWriter = new EDFWriter(stream.getWriter());
+    await edfWriter.writeHeader(createTestHeader(1, 5));
+    await edfWriter.writeRecord([new Array(10).fill(1)]);
+    await edfWriter.writeRecord([new Array(10).fill(2)]);
+    await edfWriter.close();
+
+    const buffer = new Uint8Array(chunks.reduce((n, c) => n + c.length, 0));
+    let offset = 0;
+    for (const chunk of chunks) {
+      buffer.set(chunk, offset);
+      offset += chunk.length;
+    }
+
+    const text = new TextDecoder().decode(buffer.subarray(236, 244));
+    expect(text.trim()).toBe("-1");
+
+    // The reader infers the count from the records present
+    const reader = new EDFReader(buffer);
+    expect(reader.readHeader().dataRecords).toBe(2);
+    expectToBeImprecise(reader.readValues("Signal1"), [
+      ...new Array(10).fill(1),
+      ...new Array(10).fill(2),
+    ]);
+  });
+
   it("pads signal data when too short", async () => {
     const header = createTestHeader(1, 2);
 
@@ -135,6 +218,143 @@ describe("EDFWriter", () => {
     expect(readAnnotations[1].annotation).toBe("Event A");
   });
 
+  it("fits a gap annotation for every signal", async () => {
+    // e.g. a nine channel IMU disconnecting an hour into the recording
+    const header = createTestHeader(9, 1);
+    const values = Array.from({ length: 9 }, () => new Array(10).fill(0));
+
+    const annotations: EDFAnnotation[] = header.signals.map((signal) => ({
+      onset: 3600,
+      duration: 30,
+      annotation: `Signal gap: ${signal.label}`,
+    }));
+
+    const { writer, getBuffer } = MemoryWritableStream();
+    const edfWriter = new EDFWriter(writer);
+    await edfWriter.writeHeader(header);
+    await edfWriter.writeRecord(values, annotations);
+    await edfWriter.close();
+
+    const reader = new EDFReader(await getBuffer());
+    expect(reader.readHeader().signalCount).toBe(9);
+    const readAnnotations = reader.readAnnotations();
+    expect(readAnnotations.map((a) => a.annotation)).toEqual(
+      annotations.map((a) => a.annotation),
+    );
+  });
+
+  it("drops whole annotations that don't fit, keeping the record", async () => {
+    const header = createTestHeader(1, 1);
+    const values = [[...Array(10).keys()].map((i) => i - 10)];
+
+    // Each TAL is ~40 bytes, so only a couple fit in a 128 byte record.
+    const annotations: EDFAnnotation[] = Array.from({ length: 5 }, (_, i) => ({
+      onset: 0,
+      duration: 30,
+      annotation: `Signal gap: Signal${i + 1}`,
+    }));
+
+    const warn = vi.spyOn(console, "warn").mockImplementation(() => {});
+    const { writer, getBuffer } = MemoryWritableStream();
+    const edfWriter = new EDFWriter(writer);
+    await edfWriter.writeHeader(header);
+    await edfWriter.writeRecord([values[0]], annotations);
+    await edfWriter.close();
+    expect(warn).toHaveBeenCalledOnce();
+    warn.mockRestore();
+
+    const reader = new EDFReader(await getBuffer());
+    expect(reader.readHeader().dataRecords).toBe(1);
+    expectToBeImprecise(reader.readValues("Signal1"), values[0]);
+
+    const readAnnotations = reader.readAnnotations();
+    expect(readAnnotations.length).toBeGreaterThan(0);
+    expect(readAnnotations.length).toBeLessThan(annotations.length);
+    readAnnotations.forEach((a, i) =>
+      expect(a.annotation).toBe(annotations[i].annotation),
+    );
+  });
+
+  it("interleaves signals with different samples per record", async () => {
+    const header = createTestHeader(3, 2);
+    header.recordDuration = 10;
+    // 25.6 Hz, 1 Hz and 0.1 Hz
+    header.signals[0].samplesPerRecord = 256;
+    header.signals[1].samplesPerRecord = 10;
+    header.signals[2].samplesPerRecord = 1;
+
+    const ramp = (n: number, offset: number) =>
+      Array.from({ length: n }, (_, i) => (offset + i) % 100);
+
+    const { writer, getBuffer } = MemoryWritableStream();
+    const edfWriter = new EDFWriter(writer);
+    await edfWriter.writeHeader(header);
+    for (let rec = 0; rec < 2; rec++) {
+      await edfWriter.writeRecord([
+        ramp(256, rec * 256),
+        ramp(10, rec * 10),
+        ramp(1, rec),
+      ]);
+    }
+    await edfWriter.close();
+
+    const reader = new EDFReader(await getBuffer());
+    const readHeader = reader.readHeader();
+    expect(readHeader.signals.map((s) => s.samplesPerRecord)).toEqual([
+      256, 10, 1,
+    ]);
+
+    expectToBeImprecise(reader.readValues("Signal1"), ramp(512, 0));
+    expectToBeImprecise(reader.readValues("Signal2"), ramp(20, 0));
+    expectToBeImprecise(reader.readValues("Signal3"), ramp(2, 0));
+    expectToBeImprecise(reader.readValues("Signal2", 1), ramp(10, 10));
+  });
+
+  it("rejects fractional samples per record", async () => {
+    const header = createTestHeader(2, 1);
+    header.signals[1].samplesPerRecord = 2.5;
+
+    const { writer } = MemoryWritableStream();
+    const edfWriter = new EDFWriter(writer);
+    await expect(edfWriter.writeHeader(header)).rejects.toThrow(
+      /Signal "Signal2" must have a positive integer samplesPerRecord/,
+    );
+  });
+
+  it("preserves the digital codes of a known sine", async () => {
+    // 10 Hz, 50 µV, one second per record
+    const wave = sine({
+      frequency: 10,
+      amplitude: 50,
+      sampleRate: 256,
+      length: 256,
+    });
+
+    const buffer = await writeSingleRecord(wave);
+    expect(readFirstSignalCodes(buffer, wave.length)).toEqual(
+      wave.map(expectedCode),
+    );
+
+    const reader = new EDFReader(buffer);
+    reader.readHeader();
+    const readValues = reader.readValues("Signal1");
+    expect(Math.max(...readValues)).toBeCloseTo(50, 1);
+    expect(Math.min(...readValues)).toBeCloseTo(-50, 1);
+  });
+
+  it("preserves full-scale white noise", async () => {
+    const noise = whiteNoise({ amplitude: 100, length: 256, seed: 42 });
+
+    const buffer = await writeSingleRecord(noise);
+    expect(readFirstSignalCodes(buffer, noise.length)).toEqual(
+      noise.map(expectedCode),
+    );
+
+    const reader = new EDFReader(buffer);
+    reader.readHeader();
+    expectToBeImprecise(reader.readValues("Signal1"), noise);
+  });
+
   it("writes a test signal to an EDF file", async () => {
     const recordDuration = 30;
     const records = 10;
@@ -143,9 +363,11 @@ describe("EDFWriter", () => {
     const frequency = 10;
     const amplitude = 75;
 
-    const sineWave = Array.from({ length: totalSamples }, (_, i) => {
-      const t = i / sampleRate;
-      return amplitude * Math.sin(2 * Math.PI * frequency * t);
+    const sineWave = sine({
+      frequency,
+      amplitude,
+      sampleRate,
+      length: totalSamples,
     });
 
     const header = createTestHeader(1, records);
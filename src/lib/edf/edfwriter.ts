@@ -16,7 +16,7 @@
 import type { EDFHeader, EDFSignal, EDFAnnotation } from "./edftypes";
 import { format } from "date-fns";
 
-const ANNOTATION_RECORD_LENGTH = 64; // Number of samples for annotation signal (128 bytes)
+const ANNOTATION_RECORD_LENGTH = 64; // Annotation samples per data signal (128 bytes)
 
 export class EDFWriter {
   private textEncoder = new TextEncoder();
@@ -38,9 +38,22 @@ export class EDFWriter {
       throw new Error("Header has already been written");
     }
 
-    // Clone the header to avoid mutating the original
+    // Signals may have different rates, but each must fit a whole number of
+    // samples into the common record duration.
+    header.signals.forEach(({ label, samplesPerRecord }) => {
+      if (!Number.isInteger(samplesPerRecord) || samplesPerRecord <= 0) {
+        throw new Error(
+          `Signal "${label}" must have a positive integer samplesPerRecord, got ${samplesPerRecord}`,
+        );
+      }
+    });
+
+    // Clone the header to avoid mutating the original.
+    // The record count isn't known until close(), so mark it as unknown (-1).
+    // Seekable streams get the real count rewritten on close, others keep -1.
     this.header = {
       ...header,
+      dataRecords: -1,
       signals: [...header.signals.map((s) => ({ ...s }))],
     };
 
@@ -83,6 +96,7 @@ export class EDFWriter {
       if (s === this.annSignalIndex) {
         const annText = this.generateAnnotationBlock(
           this.currentRecord,
+          samples * 2,
           annotations,
         );
         const encodedAnn = this.encodeAnnotationSignal(annText, samples * 2);
@@ -131,6 +145,9 @@ export class EDFWriter {
     );
 
     if (index === -1) {
+      // Leave room for at least one annotation per data signal in every
+      // record, e.g. when a multi-signal sensor disconnects.
+      const dataSignals = Math.max(1, this.header.signals.length);
       const annotationSignal: EDFSignal = {
         label: "EDF Annotations",
         transducerType: "",
@@ -140,7 +157,7 @@ export class EDFWriter {
         digitalMin: -32768,
         digitalMax: 32767,
         prefiltering: "",
-        samplesPerRecord: ANNOTATION_RECORD_LENGTH,
+        samplesPerRecord: ANNOTATION_RECORD_LENGTH * dataSignals,
       };
 
       this.header.signals.push(annotationSignal);
@@ -169,6 +186,7 @@ export class EDFWriter {
 
   private generateAnnotationBlock(
     recordNumber: number,
+    byteLength: number,
     annotations?: EDFAnnotation[],
   ): string {
     const startTime = recordNumber * (this.header?.recordDuration ?? 1.0);
@@ -176,12 +194,31 @@ export class EDFWriter {
 
     if (!annotations || annotations.length === 0) return text;
 
+    // Drop whole TALs that don't fit rather than truncating mid-TAL, or
+    // losing the record's sample data.
+    let bytes = this.textEncoder.encode(text).length;
+    let dropped = 0;
     for (const ann of annotations) {
-      text += `+${ann.onset.toFixed(3)}`;
+      let tal = `+${ann.onset.toFixed(3)}`;
       if (ann.duration !== undefined) {
-        text += `\u0015${ann.duration.toFixed(3)}`;
+        tal += `\u0015${ann.duration.toFixed(3)}`;
+      }
+      tal += `\u0014${ann.annotation}\u0014\u0000`;
+
+      const talBytes = this.textEncoder.encode(tal).length;
+      if (bytes + talBytes > byteLength) {
+        dropped++;
+        continue;
       }
-      text += `\u0014${ann.annotation}\u0014\u0000`;
+
+      text += tal;
+      bytes += talBytes;
+    }
+
+    if (dropped > 0) {
+      console.warn(
+        `Dropped ${dropped} annotation(s) that didn't fit in record ${recordNumber}`,
+      );
     }
 
     return text;
@@ -190,6 +227,8 @@ export class EDFWriter {
   private encodeAnnotationSignal(text: string, byteLength: number): number[] {
     const encoded = this.textEncoder.encode(text);
     const buf = new Uint8Array(byteLength);
+    // Annotations always fit, but a user supplied annotation signal may be too
+    // small for even the timekeeping TAL.
     buf.set(encoded.slice(0, byteLength));
     return Array.from(buf);
   }
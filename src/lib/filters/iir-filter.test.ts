@@ -16,6 +16,7 @@
 import { describe, it, expect } from "vitest";
 import { calcCoeffs, IIRCoeffs } from "./iir-coeffs";
 import { IIRFilter } from "./iir-filter";
+import { sine } from "@/lib/signal-gen";
 
 describe("IIRFilter", () => {
   const Fs = 48_000;
@@ -97,12 +98,18 @@ describe("IIRFilter", () => {
 
     const f = new IIRFilter(coeffs);
     const N = 4096;
-    const toneHi = Array.from({ length: N }, (_, n) =>
-      Math.sin((2 * Math.PI * 8_000 * n) / Fs),
-    );
-    const toneLo = Array.from({ length: N }, (_, n) =>
-      Math.sin((2 * Math.PI * 200 * n) / Fs),
-    );
+    const toneHi = sine({
+      frequency: 8_000,
+      amplitude: 1,
+      sampleRate: Fs,
+      length: N,
+    });
+    const toneLo = sine({
+      frequency: 200,
+      amplitude: 1,
+      sampleRate: Fs,
+      length: N,
+    });
 
     const yHi = f.simulate(toneHi);
     f.reinit();
@@ -113,4 +120,26 @@ describe("IIRFilter", () => {
 
     expect(rms(yHi)).toBeLessThan(rms(yLo));
   });
+
+  it("keeps the amplitude of a passband tone", () => {
+    // 10 Hz, 50 µV through a 30 Hz lowpass
+    const Fs = 256;
+    const coeffs = calcCoeffs({
+      Fs,
+      Fc: 30,
+      behavior: "lowpass",
+      characteristic: "butterworth",
+      order: 4,
+    });
+
+    const f = new IIRFilter(coeffs);
+    const y = f.simulate(
+      sine({ frequency: 10, amplitude: 50, sampleRate: Fs, length: 4 * Fs }),
+    );
+
+    // Skip the first second while the filter settles
+    const peak = Math.max(...y.slice(Fs).map(Math.abs));
+    expect(peak).toBeGreaterThan(49);
+    expect(peak).toBeLessThan(51);
+  });
 });
@@ -0,0 +1,89 @@
+/*
+ * Copyright (C) 2025 The OpenPSG Authors
+ *
+ * This file is licensed under the Functional Source License 1.1
+ * with a grant of AGPLv3-or-later effective two years after publication.
+ *
+ * You may not use this file except in compliance with the License.
+ * A copy of the license is available in the root of the repository
+ * and online at: https://fsl.software
+ *
+ * After two years from publication, this file may also be used under
+ * the GNU Affero General Public License, version 3 or (at your option) any
+ * later version. See <https://www.gnu.org/licenses/agpl-3.0.html> for details.
+ */
+
+import { describe, it, expect } from "vitest";
+import { resampleToGrid } from "./grid";
+import type { Values } from "@/lib/types";
+
+function makeValues(points: [number, number][]): Values {
+  return points.map(([t, value]) => ({ timestamp: new Date(t), value }));
+}
+
+const times = (values: Values) => values.map((v) => v.timestamp.getTime());
+
+describe("resampleToGrid", () => {
+  it("returns an empty array for empty input", () => {
+    expect(resampleToGrid([], 10, 100)).toEqual([]);
+  });
+
+  it("interpolates drifting timestamps onto an exact grid", () => {
+    // Nominal 10 ms period with a slightly fast clock
+    const input = makeValues([
+      [0, 0],
+      [9, 9],
+      [18, 18],
+      [27, 27],
+      [36, 36],
+    ]);
+
+    const out = resampleToGrid(input, 10, 50);
+    expect(times(out)).toEqual([0, 10, 20, 30]);
+    out.forEach((v, i) => expect(v.value).toBeCloseTo(i * 10, 10));
+  });
+
+  it("interpolates across gaps up to maxGapMs", () => {
+    const input = makeValues([
+      [0, 0],
+      [40, 4],
+    ]);
+
+    const out = resampleToGrid(input, 10, 40);
+    expect(out.map((v) => v.value)).toEqual([0, 1, 2, 3, 4]);
+  });
+
+  it("marks points inside longer gaps as invalid", () => {
+    const input = makeValues([
+      [0, 0],
+      [10, 1],
+      [60, 6],
+      [70, 7],
+    ]);
+
+    const out = resampleToGrid(input, 10, 20);
+    const values = out.map((v) => v.value);
+    expect(values[0]).toBe(0);
+    expect(values[1]).toBe(1);
+    expect(values.slice(2, 6).every(Number.isNaN)).toBe(true);
+    expect(values[6]).toBe(6);
+    expect(values[7]).toBe(7);
+  });
+
+  it("marks points before the first sample as invalid", () => {
+    const input = makeValues([
+      [15, 1],
+      [25, 2],
+    ]);
+
+    const out = resampleToGrid(input, 10, 20, 0);
+    expect(times(out)).toEqual([0, 10, 20]);
+    expect(Number.isNaN(out[0].value)).toBe(true);
+    expect(Number.isNaN(out[1].value)).toBe(true);
+    expect(out[2].value).toBeCloseTo(1.5, 10);
+  });
+
+  it("rejects non-positive periods", () => {
+    expect(() => resampleToGrid(makeValues([[0, 0]]), 0, 10)).toThrow();
+  });
+});
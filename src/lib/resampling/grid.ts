@@ -0,0 +1,81 @@
+/*
+ * Copyright (C) 2025 The OpenPSG Authors
+ *
+ * This file is licensed under the Functional Source License 1.1
+ * with a grant of AGPLv3-or-later effective two years after publication.
+ *
+ * You may not use this file except in compliance with the License.
+ * A copy of the license is available in the root of the repository
+ * and online at: https://fsl.software
+ *
+ * After two years from publication, this file may also be used under
+ * the GNU Affero General Public License, version 3 or (at your option) any
+ * later version. See <https://www.gnu.org/licenses/agpl-3.0.html> for details.
+ */
+
+import type { Value, Values } from "@/lib/types";
+
+// Resample irregularly timestamped values onto a fixed time grid.
+// Grid points are placed at startMs + k * periodMs (startMs defaults to the
+// first input timestamp) up to the last input timestamp. Each point is linearly
+// interpolated from its neighbouring input samples, as long as they are no
+// more than maxGapMs apart. Points inside a longer gap, or before the first
+// sample, are marked invalid with a NaN value.
+export function resampleToGrid(
+  input: Values,
+  periodMs: number,
+  maxGapMs: number,
+  startMs?: number,
+): Values {
+  if (periodMs <= 0) {
+    throw new Error(`periodMs must be positive, got ${periodMs}`);
+  }
+
+  const length = input.length;
+  if (length === 0) return [];
+
+  const firstTime = input[0].timestamp.getTime();
+  const endTime = input[length - 1].timestamp.getTime();
+  const start = startMs ?? firstTime;
+
+  const out: Value[] = [];
+
+  let j = 0;
+  // Multiply rather than accumulate so the grid doesn't drift over long recordings.
+  for (let k = 0; start + k * periodMs <= endTime; k++) {
+    const targetTime = start + k * periodMs;
+
+    if (targetTime < firstTime) {
+      out.push({ timestamp: new Date(targetTime), value: NaN });
+      continue;
+    }
+
+    // Advance so that input[j] <= targetTime < input[j + 1]
+    while (j < length - 1 && input[j + 1].timestamp.getTime() <= targetTime) {
+      j++;
+    }
+
+    const left = input[j];
+    const leftTime = left.timestamp.getTime();
+
+    if (targetTime === leftTime) {
+      out.push({ timestamp: new Date(targetTime), value: left.value });
+      continue;
+    }
+
+    const right = input[j + 1];
+    const rightTime = right.timestamp.getTime();
+    const gap = rightTime - leftTime;
+
+    if (gap > maxGapMs) {
+      out.push({ timestamp: new Date(targetTime), value: NaN });
+      continue;
+    }
+
+    const ratio = (targetTime - leftTime) / gap;
+    const value = left.value + (right.value - left.value) * ratio;
+    out.push({ timestamp: new Date(targetTime), value });
+  }
+
+  return out;
+}
@@ -0,0 +1,56 @@
+/*
+ * Copyright (C) 2025 The OpenPSG Authors
+ *
+ * This file is licensed under the Functional Source License 1.1
+ * with a grant of AGPLv3-or-later effective two years after publication.
+ *
+ * You may not use this file except in compliance with the License.
+ * A copy of the license is available in the root of the repository
+ * and online at: https://fsl.software
+ *
+ * After two years from publication, this file may also be used under
+ * the GNU Affero General Public License, version 3 or (at your option) any
+ * later version. See <https://www.gnu.org/licenses/agpl-3.0.html> for details.
+ */
+
+import { describe, it, expect } from "vitest";
+import { sine, whiteNoise } from "./signal-gen";
+
+describe("sine", () => {
+  it("has the requested frequency and amplitude", () => {
+    const x = sine({
+      frequency: 10,
+      amplitude: 50,
+      sampleRate: 1000,
+      length: 100,
+    });
+    expect(x).toHaveLength(100);
+    expect(x[0]).toBeCloseTo(0);
+    expect(x[25]).toBeCloseTo(50); // quarter period
+    expect(x[75]).toBeCloseTo(-50);
+    expect(Math.max(...x.map(Math.abs))).toBeCloseTo(50);
+  });
+
+  it("rejects non-positive sample rates", () => {
+    expect(() =>
+      sine({ frequency: 10, amplitude: 1, sampleRate: 0, length: 1 }),
+    ).toThrow();
+  });
+});
+
+describe("whiteNoise", () => {
+  it("stays within the amplitude with roughly zero mean", () => {
+    const x = whiteNoise({ amplitude: 5, length: 10_000 });
+    expect(x.every((v) => v >= -5 && v < 5)).toBe(true);
+    const mean = x.reduce((sum, v) => sum + v, 0) / x.length;
+    expect(Math.abs(mean)).toBeLessThan(0.2);
+  });
+
+  it("is reproducible for a given seed", () => {
+    const a = whiteNoise({ amplitude: 1, length: 16, seed: 42 });
+    const b = whiteNoise({ amplitude: 1, length: 16, seed: 42 });
+    const c = whiteNoise({ amplitude: 1, length: 16, seed: 7 });
+    expect(a).toEqual(b);
+    expect(a).not.toEqual(c);
+  });
+});
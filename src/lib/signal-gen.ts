@@ -0,0 +1,65 @@
+/*
+ * Copyright (C) 2025 The OpenPSG Authors
+ *
+ * This file is licensed under the Functional Source License 1.1
+ * with a grant of AGPLv3-or-later effective two years after publication.
+ *
+ * You may not use this file except in compliance with the License.
+ * A copy of the license is available in the root of the repository
+ * and online at: https://fsl.software
+ *
+ * After two years from publication, this file may also be used under
+ * the GNU Affero General Public License, version 3 or (at your option) any
+ * later version. See <https://www.gnu.org/licenses/agpl-3.0.html> for details.
+ */
+
+// Synthetic test signals with a known frequency and amplitude, in physical
+// units (e.g. µV), for checking that values survive filtering and EDF export.
+
+export interface ToneOptions {
+  frequency: number; // Hz
+  amplitude: number; // peak, in physical units
+  sampleRate: number; // Hz
+  length: number; // samples
+}
+
+function checkRate(sampleRate: number): void {
+  if (!Number.isFinite(sampleRate) || sampleRate <= 0) {
+    throw new Error(`sampleRate must be positive, got ${sampleRate}`);
+  }
+}
+
+export function sine({
+  frequency,
+  amplitude,
+  sampleRate,
+  length,
+}: ToneOptions): number[] {
+  checkRate(sampleRate);
+  return Array.from(
+    { length },
+    (_, i) => amplitude * Math.sin((2 * Math.PI * frequency * i) / sampleRate),
+  );
+}
+
+// Uniform white noise in [-amplitude, amplitude).
+// Seeded (mulberry32) so tests are reproducible.
+export function whiteNoise({
+  amplitude,
+  length,
+  seed = 1,
+}: {
+  amplitude: number;
+  length: number;
+  seed?: number;
+}): number[] {
+  let state = seed >>> 0;
+  const next = (): number => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+  return Array.from({ length }, () => amplitude * (2 * next() - 1));
+}